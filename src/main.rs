@@ -1,14 +1,75 @@
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::io::{stdin, BufRead};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Instant;
 
 const MINIMUM_WORD_LENGTH: usize = 3;
 
+// maps every cell to its symmetric partner; State fills the non-canonical half
+// by copying from the partner
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Symmetry {
+    Rotational180,
+    MirrorHorizontal,
+    MirrorBoth,
+    None,
+}
+
+impl Symmetry {
+    fn from_name(name: &str) -> Symmetry {
+        match name {
+            "ROTATIONAL" => Symmetry::Rotational180,
+            "MIRROR" => Symmetry::MirrorHorizontal,
+            "MIRROR_BOTH" => Symmetry::MirrorBoth,
+            "NONE" => Symmetry::None,
+            _ => panic!("unknown symmetry mode: {}", name),
+        }
+    }
+
+    // the already-determined position to copy `position` from, when it's not
+    // itself canonical
+    fn partner(&self, problem: &Problem, position: (isize, isize)) -> (isize, isize) {
+        let mirror_x = problem.size.0 as isize - 1 - position.0;
+        let mirror_y = problem.size.1 as isize - 1 - position.1;
+        match self {
+            Symmetry::Rotational180 => (mirror_x, mirror_y),
+            Symmetry::MirrorHorizontal => (mirror_x, position.1),
+            Symmetry::MirrorBoth => {
+                if position.1 * 2 > problem.size.1 as isize - 1 {
+                    (position.0, mirror_y)
+                } else {
+                    (mirror_x, position.1)
+                }
+            }
+            Symmetry::None => position,
+        }
+    }
+
+    // whether `position` is in the half State actually stores, rather than one
+    // derived from its partner
+    fn is_canonical(&self, problem: &Problem, position: (isize, isize)) -> bool {
+        match self {
+            Symmetry::Rotational180 => {
+                let field_count = problem.field_count() as isize;
+                let index = position.1 * problem.size.0 as isize + position.0;
+                index * 2 <= field_count
+            }
+            Symmetry::MirrorHorizontal => position.0 * 2 < problem.size.0 as isize,
+            Symmetry::MirrorBoth => {
+                position.0 * 2 < problem.size.0 as isize && position.1 * 2 < problem.size.1 as isize
+            }
+            Symmetry::None => true,
+        }
+    }
+}
+
 struct Problem {
     name: String,
     size: (usize, usize),
     accross: Vec<usize>,
     down: Vec<usize>,
+    symmetry: Symmetry,
 }
 
 impl Problem {
@@ -27,6 +88,11 @@ impl Problem {
 
         let name = header[0].to_owned();
         let size = header[1];
+        // the symmetry mode is an optional third header token, defaulting to
+        // the historical 180° rotation so existing puzzle files keep working
+        let symmetry = header
+            .get(2)
+            .map_or(Symmetry::Rotational180, |s| Symmetry::from_name(s));
         let accross_label = accross[0];
         let accross = accross[1];
         let down_label = down[0];
@@ -48,6 +114,7 @@ impl Problem {
             size: size,
             accross: accross,
             down: down,
+            symmetry: symmetry,
         }
     }
 
@@ -61,6 +128,10 @@ impl Problem {
     fn field_count(&self) -> usize {
         self.size.0 * self.size.1
     }
+
+    fn position_to_index(&self, position: (isize, isize)) -> usize {
+        (position.1 * self.size.0 as isize + position.0) as usize
+    }
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -86,6 +157,7 @@ struct Counts {
     reverse_down_used: usize,
 }
 
+#[derive(Clone)]
 struct State<'p> {
     problem: &'p Problem,
     fields: Vec<Field>,
@@ -132,11 +204,12 @@ impl<'p> State<'p> {
         if !self.problem.in_bounds(position) {
             return FieldEx::OutOfBounds;
         }
-        let field_count = self.problem.field_count();
-        let mut index = (position.1 * self.problem.size.0 as isize + position.0) as usize;
-        if index > field_count / 2 {
-            index = field_count - 1 - index;
-        }
+        let canonical = if self.problem.symmetry.is_canonical(self.problem, position) {
+            position
+        } else {
+            self.problem.symmetry.partner(self.problem, position)
+        };
+        let index = self.problem.position_to_index(canonical);
         self.fields
             .get(index)
             .map_or(FieldEx::Unfilled, |f| FieldEx::Field(*f))
@@ -160,7 +233,7 @@ impl<'p> State<'p> {
 
     fn new(problem: &'p Problem) -> Self {
         State {
-            problem: problem,
+            problem,
             fields: Vec::<_>::new(),
             counts: vec![Counts {
                 number: 1,
@@ -179,32 +252,45 @@ impl<'p> State<'p> {
         }
     }
 
-    fn push(&mut self, field: Field) -> Result<(), RuleViolation> {
-        let remaining = (self.problem.size.0 * self.problem.size.1) as isize
-            - (self.fields.len() * 2 + 1) as isize;
-        assert!(remaining >= 0);
-        self.push_one(field)?;
-        if remaining == 0 {
-            for i in (0..self.fields.len() - 1).rev() {
-                let field = self.fields[i];
-                self.push_one(field)?;
+    // position of the next cell push_one would fill, in reading order
+    fn next_position(&self) -> (isize, isize) {
+        (
+            (self.fields.len() % self.problem.size.0) as isize,
+            (self.fields.len() / self.problem.size.0) as isize,
+        )
+    }
+
+    // derives and pushes every non-canonical cell from here up to the next
+    // canonical one; canonical/non-canonical cells can interleave within a row
+    // (e.g. under a left-right mirror), so this runs before *and* after every
+    // caller-supplied push, not just once at the halfway point
+    fn fill_non_canonical(&mut self) -> Result<(), RuleViolation> {
+        while self.fields.len() < self.problem.field_count() {
+            let position = self.next_position();
+            if self.problem.symmetry.is_canonical(self.problem, position) {
+                break;
             }
+            let partner = self.problem.symmetry.partner(self.problem, position);
+            let field = self.at(partner);
+            self.push_one(field)?;
         }
         Ok(())
     }
 
+    fn push(&mut self, field: Field) -> Result<(), RuleViolation> {
+        self.fill_non_canonical()?;
+        assert!(self.problem.symmetry.is_canonical(self.problem, self.next_position()));
+        self.push_one(field)?;
+        self.fill_non_canonical()?;
+        Ok(())
+    }
+
     fn push_one(&mut self, field: Field) -> Result<(), RuleViolation> {
         let problem = self.problem;
-        let position = (
-            (self.fields.len() % problem.size.0) as isize,
-            (self.fields.len() / problem.size.0) as isize,
-        );
+        let position = self.next_position();
         assert!(problem.in_bounds(position));
-        if position.1 * 2 >= problem.size.1 as isize {
-            let expected = self.at((
-                problem.size.0 as isize - 1 - position.0,
-                problem.size.1 as isize - 1 - position.1,
-            ));
+        if !problem.symmetry.is_canonical(problem, position) {
+            let expected = self.at(problem.symmetry.partner(problem, position));
             assert!(field == expected);
         }
         self.fields.push(field);
@@ -281,9 +367,18 @@ impl<'p> State<'p> {
             }
         }
 
-        // check the mirrored position. We have to go one row further down, since the numbers are only determined once the row above is filled
-        // relative positions are negated, since we're looking at the upper left version of the board, when we actually check the rules for the lower right version of the board
-        if self.at((position.0, position.1 - 1)) == Field::White {
+        // Under 180° rotation, reading the grid backwards is reading the same grid,
+        // so the clue numbering read in reverse must match the same accross/down
+        // lists read backwards. That trick is specific to point-reflection: a
+        // left-right or both-axis mirror doesn't turn "traverse in reverse" into
+        // "read the mirrored grid", so only Rotational180 gets this extra check.
+        if problem.symmetry == Symmetry::Rotational180
+            && self.at((position.0, position.1 - 1)) == Field::White
+        {
+            // We have to go one row further down, since the numbers are only
+            // determined once the row above is filled. Relative positions are
+            // negated, since we're looking at the upper left version of the
+            // board, when we actually check the rules for the lower right version.
             let position = (position.0, position.1 - 1);
 
             let mut numbered = false;
@@ -361,22 +456,519 @@ impl<'p> Debug for State<'p> {
     }
 }
 
+// Packed alternative to State, used by the --bits search mode.
+
+const BITS_PER_WORD: usize = 64;
+
+fn bitboard_words(bits: usize) -> usize {
+    bits.div_ceil(BITS_PER_WORD)
+}
+
+fn bit_get(words: &[u64], index: usize) -> bool {
+    words[index / BITS_PER_WORD] & (1u64 << (index % BITS_PER_WORD)) != 0
+}
+
+fn bit_set(words: &mut [u64], index: usize) {
+    words[index / BITS_PER_WORD] |= 1u64 << (index % BITS_PER_WORD);
+}
+
+// Packed alternative to State: every cell is a bit in `black` instead of a
+// Vec<Field> (a cell is binary, so tracking "is it black" is enough). Stores only
+// the canonical half; Rotational180 only, since that's the only mode whose
+// canonical half is a contiguous prefix (BitState::new enforces it).
+#[derive(Clone)]
+struct Bitboard {
+    black: Vec<u64>,
+    half_len: usize,
+    len: usize,
+}
+
+impl Bitboard {
+    fn new(problem: &Problem) -> Self {
+        let half_len = problem.field_count() / 2 + 1;
+        Bitboard {
+            black: vec![0; bitboard_words(half_len)],
+            half_len,
+            len: 0,
+        }
+    }
+
+    fn try_at(&self, problem: &Problem, position: (isize, isize)) -> FieldEx {
+        if !problem.in_bounds(position) {
+            return FieldEx::OutOfBounds;
+        }
+        let canonical = if problem.symmetry.is_canonical(problem, position) {
+            position
+        } else {
+            problem.symmetry.partner(problem, position)
+        };
+        let index = problem.position_to_index(canonical);
+        if index >= self.len {
+            return FieldEx::Unfilled;
+        }
+        if bit_get(&self.black, index) {
+            FieldEx::Field(Field::Black)
+        } else {
+            FieldEx::Field(Field::White)
+        }
+    }
+
+    // out-of-bounds counts as Black, matching State::at
+    fn is_black_at(&self, problem: &Problem, position: (isize, isize)) -> bool {
+        match self.try_at(problem, position) {
+            FieldEx::Field(Field::Black) | FieldEx::OutOfBounds => true,
+            FieldEx::Field(Field::White) => false,
+            FieldEx::Unfilled => unreachable!("unfilled"),
+        }
+    }
+
+    // White run immediately left of `position`, capped at MINIMUM_WORD_LENGTH
+    fn white_run_before(&self, problem: &Problem, position: (isize, isize)) -> usize {
+        let mut run = 0;
+        for i in 1..=MINIMUM_WORD_LENGTH as isize {
+            if self.is_black_at(problem, (position.0 - i, position.1)) {
+                break;
+            }
+            run += 1;
+        }
+        run
+    }
+
+    // is there room for MINIMUM_WORD_LENGTH cells between `column` and the row edge
+    fn row_has_room(&self, problem: &Problem, column: usize) -> bool {
+        problem.size.0 - column >= MINIMUM_WORD_LENGTH
+    }
+
+    // BitState::push also pushes the derived cells past half_len; those are only
+    // counted in `len`, since try_at reads them from their canonical partner
+    fn push(&mut self, field: Field) {
+        if field == Field::Black && self.len < self.half_len {
+            bit_set(&mut self.black, self.len);
+        }
+        self.len += 1;
+    }
+}
+
+// State, but checking push_one's rules against a Bitboard instead of Scan
+#[derive(Clone)]
+struct BitState<'p> {
+    problem: &'p Problem,
+    board: Bitboard,
+    counts: Vec<Counts>,
+}
+
+impl<'p> BitState<'p> {
+    fn new(problem: &'p Problem) -> Self {
+        assert_eq!(
+            problem.symmetry,
+            Symmetry::Rotational180,
+            "BitState only supports Symmetry::Rotational180; use State for other modes"
+        );
+        BitState {
+            problem,
+            board: Bitboard::new(problem),
+            counts: vec![Counts {
+                number: 1,
+                accross_used: 0,
+                down_used: 0,
+                reverse_number: problem
+                    .accross
+                    .iter()
+                    .chain(problem.down.iter())
+                    .copied()
+                    .max()
+                    .unwrap(),
+                reverse_down_used: 0,
+                reverse_accross_used: 0,
+            }],
+        }
+    }
+
+    fn is_final(&self) -> bool {
+        self.board.len == self.problem.field_count()
+    }
+
+    fn push(&mut self, field: Field) -> Result<(), RuleViolation> {
+        assert!(self.board.len < self.board.half_len);
+        self.push_one(field)?;
+        if self.board.len == self.board.half_len {
+            // the canonical half is fully filled; derive every remaining cell from
+            // its already-stored partner instead of taking it as input. The
+            // partner of any non-canonical cell under Rotational180 always falls
+            // in the already-stored half, regardless of whether `field_count` is
+            // odd or even, so this doesn't need its own odd/even case.
+            while self.board.len < self.problem.field_count() {
+                let position = (
+                    (self.board.len % self.problem.size.0) as isize,
+                    (self.board.len / self.problem.size.0) as isize,
+                );
+                let partner = self.problem.symmetry.partner(self.problem, position);
+                let field = if self.board.is_black_at(self.problem, partner) {
+                    Field::Black
+                } else {
+                    Field::White
+                };
+                self.push_one(field)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn push_one(&mut self, field: Field) -> Result<(), RuleViolation> {
+        let problem = self.problem;
+        let position = (
+            (self.board.len % problem.size.0) as isize,
+            (self.board.len / problem.size.0) as isize,
+        );
+        assert!(problem.in_bounds(position));
+        if !problem.symmetry.is_canonical(problem, position) {
+            let expected = self
+                .board
+                .is_black_at(problem, problem.symmetry.partner(problem, position));
+            assert!((field == Field::Black) == expected);
+        }
+        self.board.push(field);
+        let mut counts = self.counts.last().unwrap().clone();
+
+        match field {
+            Field::White => {
+                let mut numbered = false;
+                if self.board.is_black_at(problem, (position.0 - 1, position.1)) {
+                    // starts a new "accross" word
+                    numbered = true;
+                    if self.problem.accross.get(counts.accross_used).copied() != Some(counts.number)
+                    {
+                        return Err(RuleViolation::NumberWrongAccross);
+                    }
+                    counts.accross_used += 1;
+                    if !self.board.row_has_room(problem, position.0 as usize) {
+                        return Err(RuleViolation::TooLittleSpaceAccross);
+                    }
+                }
+                if self.board.is_black_at(problem, (position.0, position.1 - 1)) {
+                    // starts a new "down" word
+                    numbered = true;
+                    if self.problem.down.get(counts.down_used).copied() != Some(counts.number) {
+                        return Err(RuleViolation::NumberWrongDown);
+                    }
+                    counts.down_used += 1;
+                    let down_white = (0..MINIMUM_WORD_LENGTH)
+                        .take_while(|i| {
+                            matches!(
+                                self.board
+                                    .try_at(problem, (position.0, position.1 + *i as isize)),
+                                FieldEx::Field(Field::White) | FieldEx::Unfilled
+                            )
+                        })
+                        .count();
+                    if down_white < MINIMUM_WORD_LENGTH {
+                        return Err(RuleViolation::TooLittleSpaceDown);
+                    }
+                }
+                if numbered {
+                    counts.number += 1;
+                }
+            }
+            Field::Black => {
+                // check if word on the left satisfies minimum word length: one bit
+                // test per cell, for up to MINIMUM_WORD_LENGTH cells to the left
+                let left_white = self.board.white_run_before(problem, position);
+                if left_white != 0 && left_white < MINIMUM_WORD_LENGTH {
+                    return Err(RuleViolation::WordTooShortAccross);
+                }
+
+                // check if word above satisfies minimum word length: a single bit
+                // test per row, since columns aren't contiguous in the bitboard
+                let up_white = (1..=MINIMUM_WORD_LENGTH)
+                    .take_while(|i| {
+                        matches!(
+                            self.board
+                                .try_at(problem, (position.0, position.1 - *i as isize)),
+                            FieldEx::Field(Field::White)
+                        )
+                    })
+                    .count();
+                if up_white != 0 && up_white < MINIMUM_WORD_LENGTH {
+                    return Err(RuleViolation::WordTooShortDown);
+                }
+            }
+        }
+
+        // mirrored reverse-numbering check, same as `State::push_one`
+        if !self.board.is_black_at(problem, (position.0, position.1 - 1)) {
+            let position = (position.0, position.1 - 1);
+
+            let mut numbered = false;
+            if self.board.is_black_at(problem, (position.0, position.1 + 1)) {
+                numbered = true;
+                if self
+                    .problem
+                    .down
+                    .get(problem.down.len() - 1 - counts.reverse_down_used)
+                    .copied()
+                    != Some(counts.reverse_number)
+                {
+                    return Err(RuleViolation::NumberWrongDownReverse);
+                }
+                counts.reverse_down_used += 1;
+            }
+            if self.board.is_black_at(problem, (position.0 + 1, position.1)) {
+                numbered = true;
+                if self
+                    .problem
+                    .accross
+                    .get(problem.accross.len() - 1 - counts.reverse_accross_used)
+                    .copied()
+                    != Some(counts.reverse_number)
+                {
+                    return Err(RuleViolation::NumberWrongAccrossReverse);
+                }
+                counts.reverse_accross_used += 1;
+            }
+            if numbered {
+                counts.reverse_number -= 1;
+            }
+        }
+
+        if self.board.len == problem.field_count() {
+            if counts.accross_used != problem.accross.len() {
+                return Err(RuleViolation::LeftOverAccross);
+            }
+            if counts.down_used != problem.down.len() {
+                return Err(RuleViolation::LeftOverDown);
+            }
+        }
+
+        self.counts.push(counts);
+        Ok(())
+    }
+}
+
+impl<'p> Debug for BitState<'p> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for y in 0..self.problem.size.1 {
+            for x in 0..self.problem.size.0 {
+                let field = self.board.try_at(self.problem, (x as isize, y as isize));
+                match field {
+                    FieldEx::Field(field) => match field {
+                        Field::White => f.write_str(". ")?,
+                        Field::Black => f.write_str("# ")?,
+                    },
+                    FieldEx::OutOfBounds => unreachable!("out of bounds"),
+                    FieldEx::Unfilled => f.write_str("? ")?,
+                }
+            }
+            f.write_str("\r\n")?;
+        }
+        f.write_str("\r\n")?;
+        Ok(())
+    }
+}
+
+// vertical White run ending at the row above, capped at MINIMUM_WORD_LENGTH
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+enum ColumnRun {
+    Black,
+    White(usize),
+}
+
+// frontier state between two rows; partial fillings with the same FrontierKey are
+// interchangeable from here down, so count_solutions_by_rows merges their counts
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct FrontierKey {
+    columns: Vec<ColumnRun>,
+    accross_used: usize,
+    down_used: usize,
+    number: usize,
+}
+
+// extends a partial row from `col`, enforcing the same rules as State::push_one,
+// and records every way to complete it (with the resulting ColumnRuns) into row_results
+#[allow(clippy::too_many_arguments)]
+fn extend_row(
+    problem: &Problem,
+    row: usize,
+    prev_columns: &[ColumnRun],
+    col: usize,
+    left: Option<Field>,
+    horizontal_white_run: usize,
+    accross_used: usize,
+    down_used: usize,
+    number: usize,
+    new_columns: &mut Vec<ColumnRun>,
+    row_results: &mut HashMap<FrontierKey, u64>,
+) {
+    if col == problem.size.0 {
+        let key = FrontierKey {
+            columns: new_columns.clone(),
+            accross_used,
+            down_used,
+            number,
+        };
+        *row_results.entry(key).or_insert(0) += 1;
+        return;
+    }
+
+    // left == None only at the row's left edge, which acts like a Black neighbor,
+    // same as `State::at` mapping out-of-bounds positions to Field::Black.
+    let left_is_black = left != Some(Field::White);
+
+    for field in [Field::White, Field::Black] {
+        let mut accross_used = accross_used;
+        let mut down_used = down_used;
+        let mut number = number;
+        let mut numbered = false;
+
+        match field {
+            Field::White => {
+                if left_is_black {
+                    // starts a new "accross" word
+                    if problem.accross.get(accross_used).copied() != Some(number) {
+                        continue;
+                    }
+                    accross_used += 1;
+                    if problem.size.0 - col < MINIMUM_WORD_LENGTH {
+                        continue;
+                    }
+                    numbered = true;
+                }
+                if prev_columns[col] == ColumnRun::Black {
+                    // starts a new "down" word
+                    if problem.down.get(down_used).copied() != Some(number) {
+                        continue;
+                    }
+                    down_used += 1;
+                    if problem.size.1 - row < MINIMUM_WORD_LENGTH {
+                        continue;
+                    }
+                    numbered = true;
+                }
+            }
+            Field::Black => {
+                // word on the left must either be empty or reach the minimum length
+                if horizontal_white_run != 0 && horizontal_white_run < MINIMUM_WORD_LENGTH {
+                    continue;
+                }
+                // word above must either be empty or reach the minimum length
+                if let ColumnRun::White(len) = prev_columns[col] {
+                    if len < MINIMUM_WORD_LENGTH {
+                        continue;
+                    }
+                }
+            }
+        }
+        if numbered {
+            number += 1;
+        }
+
+        new_columns.push(match field {
+            Field::White => match prev_columns[col] {
+                ColumnRun::Black => ColumnRun::White(1),
+                ColumnRun::White(len) => ColumnRun::White((len + 1).min(MINIMUM_WORD_LENGTH)),
+            },
+            Field::Black => ColumnRun::Black,
+        });
+        let next_horizontal_run = match field {
+            Field::White => horizontal_white_run + 1,
+            Field::Black => 0,
+        };
+        extend_row(
+            problem,
+            row,
+            prev_columns,
+            col + 1,
+            Some(field),
+            next_horizontal_run,
+            accross_used,
+            down_used,
+            number,
+            new_columns,
+            row_results,
+        );
+        new_columns.pop();
+    }
+}
+
+// counts solutions row by row, memoizing the FrontierKey reached after each row
+// instead of recursing cell by cell. Unlike State, doesn't enforce any symmetry,
+// so it only matches search_problem on a Symmetry::None problem.
+fn count_solutions_by_rows(problem: &Problem) -> u64 {
+    assert_eq!(
+        problem.symmetry,
+        Symmetry::None,
+        "count_solutions_by_rows only supports Symmetry::None; use State for other modes"
+    );
+    let width = problem.size.0;
+    let mut frontier = HashMap::new();
+    frontier.insert(
+        FrontierKey {
+            columns: vec![ColumnRun::Black; width],
+            accross_used: 0,
+            down_used: 0,
+            number: 1,
+        },
+        1u64,
+    );
+
+    for row in 0..problem.size.1 {
+        let mut next_frontier: HashMap<FrontierKey, u64> = HashMap::new();
+        for (key, weight) in &frontier {
+            let mut row_results = HashMap::new();
+            let mut new_columns = Vec::with_capacity(width);
+            extend_row(
+                problem,
+                row,
+                &key.columns,
+                0,
+                None,
+                0,
+                key.accross_used,
+                key.down_used,
+                key.number,
+                &mut new_columns,
+                &mut row_results,
+            );
+            for (new_key, count) in row_results {
+                *next_frontier.entry(new_key).or_insert(0) += count * weight;
+            }
+        }
+        frontier = next_frontier;
+    }
+
+    // below the last row is an implicit Black boundary, same as `State::at`
+    // mapping out-of-bounds positions to Field::Black
+    frontier
+        .into_iter()
+        .filter(|(key, _)| {
+            key.accross_used == problem.accross.len()
+                && key.down_used == problem.down.len()
+                && key
+                    .columns
+                    .iter()
+                    .all(|c| !matches!(c, ColumnRun::White(len) if *len < MINIMUM_WORD_LENGTH))
+        })
+        .map(|(_, count)| count)
+        .sum()
+}
+
 struct SearchState {
     start: Instant,
     solution_count: u64,
     error_count: u64,
 }
 
-fn search<'p>(state: &mut State<'p>, search_state: &mut SearchState) {
+fn search<'p>(state: &mut State<'p>, search_state: &mut SearchState, print_solutions: bool) {
     let len = state.fields.len();
     for field in [Field::White, Field::Black].iter() {
         match state.push(*field) {
             Ok(_) => {
                 if state.is_final() {
                     search_state.solution_count += 1;
-                    println!("{:?}", state);
+                    if print_solutions {
+                        println!("{:?}", state);
+                    }
                 } else {
-                    search(state, search_state);
+                    search(state, search_state, print_solutions);
                 }
             }
             Err(error) => {
@@ -406,10 +998,143 @@ fn search_problem<'p>(problem: &'p Problem) -> SearchState {
         error_count: 0,
     };
     let mut state = State::new(problem);
-    search(&mut state, &mut search_state);
+    search(&mut state, &mut search_state, true);
     search_state
 }
 
+// BitState can't truncate a push (bits are only ever set, never cleared), so unlike
+// `search` this clones a new BitState per branch instead of backtracking in place
+fn search_bits(state: &BitState, search_state: &mut SearchState, print_solutions: bool) {
+    for field in [Field::White, Field::Black] {
+        let mut next = state.clone();
+        match next.push(field) {
+            Ok(()) => {
+                if next.is_final() {
+                    search_state.solution_count += 1;
+                    if print_solutions {
+                        println!("{:?}", next);
+                    }
+                } else {
+                    search_bits(&next, search_state, print_solutions);
+                }
+            }
+            Err(error) => {
+                search_state.error_count += 1;
+                if search_state.error_count % 10_000_000 == 0 {
+                    eprintln!(
+                        "Solutions: {}, Elapsed: {}s, Errors: {}M, {:?}",
+                        search_state.solution_count,
+                        search_state.start.elapsed().as_secs(),
+                        search_state.error_count / 1_000_000,
+                        error
+                    );
+                    eprintln!("{:?}", next);
+                }
+            }
+        }
+    }
+}
+
+fn search_problem_bits(problem: &Problem) -> SearchState {
+    let mut search_state = SearchState {
+        start: Instant::now(),
+        solution_count: 0,
+        error_count: 0,
+    };
+    search_bits(&BitState::new(problem), &mut search_state, true);
+    search_state
+}
+
+// collects every State with `depth` cells filled, to hand off to
+// search_problem_parallel's worker threads; states that finish early are
+// counted directly instead of being handed off
+fn collect_prefixes<'p>(
+    state: &mut State<'p>,
+    depth: usize,
+    prefixes: &mut Vec<State<'p>>,
+    solution_count: &mut u64,
+    error_count: &mut u64,
+) {
+    if state.is_final() {
+        *solution_count += 1;
+        return;
+    }
+    if state.fields.len() >= depth {
+        prefixes.push(state.clone());
+        return;
+    }
+
+    let len = state.fields.len();
+    for field in [Field::White, Field::Black] {
+        match state.push(field) {
+            Ok(_) => collect_prefixes(state, depth, prefixes, solution_count, error_count),
+            Err(_) => *error_count += 1,
+        }
+        state.fields.truncate(len);
+        state.counts.truncate(len + 1);
+    }
+}
+
+fn available_parallelism() -> usize {
+    std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+}
+
+// enumerates every assignment of the first prefix_depth cells sequentially, then
+// explores the surviving subtrees across thread_count worker threads; counts match
+// search_problem's
+fn search_problem_parallel(
+    problem: &Problem,
+    prefix_depth: usize,
+    thread_count: usize,
+    print_solutions: bool,
+) -> SearchState {
+    let start = Instant::now();
+    let mut prefix_state = State::new(problem);
+    let mut prefixes = Vec::new();
+    let mut solution_count = 0u64;
+    let mut error_count = 0u64;
+    collect_prefixes(
+        &mut prefix_state,
+        prefix_depth.min(problem.field_count()),
+        &mut prefixes,
+        &mut solution_count,
+        &mut error_count,
+    );
+
+    let solution_count = AtomicU64::new(solution_count);
+    let error_count = AtomicU64::new(error_count);
+    let thread_count = thread_count.max(1);
+    let chunk_size = prefixes.len().div_ceil(thread_count);
+
+    std::thread::scope(|scope| {
+        let solution_count = &solution_count;
+        let error_count = &error_count;
+        for chunk in prefixes.chunks(chunk_size.max(1)) {
+            scope.spawn(move || {
+                for prefix in chunk {
+                    let mut state = prefix.clone();
+                    let mut local_search_state = SearchState {
+                        start,
+                        solution_count: 0,
+                        error_count: 0,
+                    };
+                    search(&mut state, &mut local_search_state, print_solutions);
+                    solution_count.fetch_add(local_search_state.solution_count, Ordering::Relaxed);
+                    error_count.fetch_add(local_search_state.error_count, Ordering::Relaxed);
+                }
+            });
+        }
+    });
+
+    SearchState {
+        start,
+        solution_count: solution_count.load(Ordering::Relaxed),
+        error_count: error_count.load(Ordering::Relaxed),
+    }
+}
+
 fn check_example_solution() {
     let problem_text =
         "EXAMPLE: 15x15
@@ -435,9 +1160,143 @@ fn check_example_solution() {
     }
 }
 
+// checks BitState against State on an odd- and an even-field_count board
+fn check_bit_state() {
+    fn count_with_state(problem: &Problem) -> (u64, u64) {
+        let mut search_state = SearchState {
+            start: Instant::now(),
+            solution_count: 0,
+            error_count: 0,
+        };
+        search(&mut State::new(problem), &mut search_state, false);
+        (search_state.solution_count, search_state.error_count)
+    }
+
+    fn count_with_bitstate(problem: &Problem) -> (u64, u64) {
+        fn rec(state: &BitState, solution_count: &mut u64, error_count: &mut u64) {
+            for field in [Field::White, Field::Black] {
+                let mut next = state.clone();
+                match next.push(field) {
+                    Ok(()) => {
+                        if next.is_final() {
+                            *solution_count += 1;
+                        } else {
+                            rec(&next, solution_count, error_count);
+                        }
+                    }
+                    Err(_) => *error_count += 1,
+                }
+            }
+        }
+        let mut solution_count = 0;
+        let mut error_count = 0;
+        rec(&BitState::new(problem), &mut solution_count, &mut error_count);
+        (solution_count, error_count)
+    }
+
+    for text in [
+        "ODD: 5x5: ROTATIONAL\nA: 1,6,7,8,9\nD: 1,2,3,4,5",
+        "EVEN: 4x4: ROTATIONAL\nA: 1,3,4,7\nD: 1,2,3,6",
+    ] {
+        let problem = Problem::load(&mut text.as_bytes());
+        let expected = count_with_state(&problem);
+        let actual = count_with_bitstate(&problem);
+        assert_eq!(
+            actual, expected,
+            "BitState mismatch on {}: got {:?}, expected {:?}",
+            text, actual, expected
+        );
+    }
+}
+
+// checks count_solutions_by_rows against search_problem on a Symmetry::None board,
+// since count_solutions_by_rows doesn't enforce any symmetry itself
+fn check_row_profile_counter() {
+    let text = "ROWS: 4x4: NONE\nA: 1,3,4,7\nD: 1,2,3,6";
+    let problem = Problem::load(&mut text.as_bytes());
+    let expected = search_problem(&problem).solution_count;
+    let actual = count_solutions_by_rows(&problem);
+    assert_eq!(
+        actual, expected,
+        "count_solutions_by_rows mismatch: got {}, expected {}",
+        actual, expected
+    );
+}
+
+// checks search_problem_parallel's total counts against the sequential search_problem
+fn check_parallel_search() {
+    let text = "PARALLEL: 5x5: ROTATIONAL\nA: 1,6,7,8,9\nD: 1,2,3,4,5";
+    let problem = Problem::load(&mut text.as_bytes());
+    let expected = search_problem(&problem);
+    let actual = search_problem_parallel(&problem, 2, available_parallelism(), false);
+    assert_eq!(
+        actual.solution_count, expected.solution_count,
+        "search_problem_parallel solution_count mismatch"
+    );
+    assert_eq!(
+        actual.error_count, expected.error_count,
+        "search_problem_parallel error_count mismatch"
+    );
+}
+
+// finds the first solution via the same push/backtrack as `search`, stopping
+// immediately instead of enumerating every solution
+fn find_first_solution<'p>(state: &mut State<'p>) -> Option<State<'p>> {
+    let len = state.fields.len();
+    for field in [Field::White, Field::Black] {
+        if state.push(field).is_ok() {
+            if state.is_final() {
+                return Some(state.clone());
+            }
+            if let Some(found) = find_first_solution(state) {
+                return Some(found);
+            }
+        }
+        state.fields.truncate(len);
+        state.counts.truncate(len + 1);
+    }
+    None
+}
+
+// checks that a real (non-uniform) MIRROR/MIRROR_BOTH solution's cells agree with
+// their geometric mirror partner, computed independently of Symmetry::partner
+fn check_mirror_symmetry() {
+    for (text, mirror_y_too) in [
+        ("MH: 5x5: MIRROR\nA: 1,4,6,7,8\nD: 1,2,3,4,5", false),
+        ("MB: 5x5: MIRROR_BOTH\nA: 1,4,6,7,8\nD: 1,2,3,4,5", true),
+    ] {
+        let problem = Problem::load(&mut text.as_bytes());
+        let mut state = State::new(&problem);
+        let solution = find_first_solution(&mut state).expect("expected a solution");
+
+        let mut saw_black = false;
+        for y in 0..problem.size.1 as isize {
+            for x in 0..problem.size.0 as isize {
+                let mirror_x = problem.size.0 as isize - 1 - x;
+                let mirror_y = if mirror_y_too { problem.size.1 as isize - 1 - y } else { y };
+                let field = solution.at((x, y));
+                let mirror_field = solution.at((mirror_x, mirror_y));
+                assert_eq!(
+                    field, mirror_field,
+                    "{}: ({}, {}) != mirror ({}, {})",
+                    text, x, y, mirror_x, mirror_y
+                );
+                saw_black |= field == Field::Black;
+            }
+        }
+        assert!(saw_black, "{}: solution has no black cells, test is trivial", text);
+    }
+}
+
 fn main() {
     check_example_solution();
+    check_bit_state();
+    check_row_profile_counter();
+    check_parallel_search();
+    check_mirror_symmetry();
 
+    // optional mode selector, e.g. `crosswords --rows < puzzle.txt`
+    let mode = std::env::args().nth(1);
     let problem = Problem::load(&mut stdin().lock());
     println!(
         "Problem: {} ({}x{})",
@@ -445,8 +1304,27 @@ fn main() {
     );
     println!();
 
-    let result = search_problem(&problem);
-    println!("solutions: {}", result.solution_count);
-    println!("errors: {}", result.error_count);
-    println!("duration: {:?}", result.start.elapsed());
+    match mode.as_deref() {
+        Some("--rows") => {
+            println!("solutions: {}", count_solutions_by_rows(&problem));
+        }
+        Some("--parallel") => {
+            let result = search_problem_parallel(&problem, 2, available_parallelism(), true);
+            println!("solutions: {}", result.solution_count);
+            println!("errors: {}", result.error_count);
+            println!("duration: {:?}", result.start.elapsed());
+        }
+        Some("--bits") => {
+            let result = search_problem_bits(&problem);
+            println!("solutions: {}", result.solution_count);
+            println!("errors: {}", result.error_count);
+            println!("duration: {:?}", result.start.elapsed());
+        }
+        _ => {
+            let result = search_problem(&problem);
+            println!("solutions: {}", result.solution_count);
+            println!("errors: {}", result.error_count);
+            println!("duration: {:?}", result.start.elapsed());
+        }
+    }
 }